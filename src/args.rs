@@ -14,6 +14,13 @@ impl CallArgs {
     pub async fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
         match self.privileges {
             | Privilege::Normal => match &self.command {
+                | Command::Render(args) if args.plain.is_plain && args.shell_trust == ShellTrust::Prompt => {
+                    Err(Box::new(Error::Argument(
+                        "plain mode never blocks on a TTY: --shell-trust prompt is not allowed together with \
+                         COMPLATE_PLAIN (use ultimate or none instead)"
+                            .into(),
+                    )))
+                },
                 | _ => Ok(()),
             },
             | Privilege::Experimental => Ok(()),
@@ -21,6 +28,28 @@ impl CallArgs {
     }
 }
 
+#[derive(Debug, Default, Clone)]
+pub struct PlainInfo {
+    pub is_plain: bool,
+    pub except: Vec<String>,
+}
+
+impl PlainInfo {
+    pub fn from_env() -> Self {
+        let is_plain = std::env::var("COMPLATE_PLAIN").is_ok();
+        let except = std::env::var("COMPLATE_PLAINEXCEPT")
+            .ok()
+            .map(|v| v.split(',').map(|s| s.trim().to_owned()).filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+
+        Self { is_plain, except }
+    }
+
+    pub fn excepts(&self, feature: &str) -> bool {
+        self.except.iter().any(|v| v == feature)
+    }
+}
+
 #[derive(Debug)]
 pub enum Privilege {
     Normal,
@@ -36,7 +65,11 @@ pub enum ManualFormat {
 #[derive(Debug)]
 pub enum Command {
     Manual { path: String, format: ManualFormat },
-    Autocomplete { path: String, shell: clap_complete::Shell },
+    Autocomplete {
+        path: String,
+        shell: clap_complete::Shell,
+        template_ids: Vec<String>,
+    },
     Init,
     Render(RenderArguments),
 }
@@ -49,14 +82,38 @@ pub struct RenderArguments {
     pub shell_trust: ShellTrust,
     pub loose: bool,
     pub backend: Backend,
+    pub plain: PlainInfo,
 }
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum ShellTrust {
     None,
+    Prompt,
     Ultimate,
 }
 
+impl ShellTrust {
+    pub fn confirm(&self, command: &str) -> std::io::Result<bool> {
+        match self {
+            | ShellTrust::None => Ok(false),
+            | ShellTrust::Ultimate => Ok(true),
+            | ShellTrust::Prompt => {
+                use std::io::Write;
+
+                print!("about to execute shell command: `{}` - allow? [y/N] ", command);
+                std::io::stdout().flush()?;
+                let mut answer = String::new();
+                std::io::stdin().read_line(&mut answer)?;
+                Ok(Self::answer_confirms(&answer))
+            },
+        }
+    }
+
+    fn answer_confirms(answer: &str) -> bool {
+        matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+    }
+}
+
 #[derive(Debug)]
 pub enum Backend {
     Headless,
@@ -112,7 +169,12 @@ impl ClapArgumentLoader {
                     .short('s')
                     .long("shell")
                     .value_parser(["bash", "zsh", "fish", "elvish", "powershell"])
-                    .required(true)))
+                    .required(true))
+                .arg(clap::Arg::new("config")
+                    .short('c')
+                    .long("config")
+                    .help("When given, the config's template identifiers are injected into the \
+                           \"-t/--template\" completion instead of generic flag help.")))
             .subcommand(clap::Command::new("init")
                 .about("Initializes a dummy default configuration in \"./.complate/config.yaml\"."))
             .subcommand(clap::Command::new("render")
@@ -120,15 +182,26 @@ impl ClapArgumentLoader {
                 .arg(clap::Arg::new("config")
                     .short('c')
                     .long("config")
-                    .help("The configuration file to use.")
-                    .default_value("./.complate/config.yaml"))
+                    .help("The configuration file to use. When omitted, the nearest \"./.complate/config.yaml\" is \
+                           discovered by walking up from the current directory."))
+                .arg(clap::Arg::new("no-discover")
+                    .long("no-discover")
+                    .action(ArgAction::SetTrue)
+                    .help("Disables upward directory discovery and uses \"./.complate/config.yaml\" literally."))
                 .arg(clap::Arg::new("template")
                     .short('t')
                     .long("template")
                     .help("Specify the template to use from the config and skip it's selection."))
+                .arg(clap::Arg::new("shell-trust")
+                    .long("shell-trust")
+                    .help("The trust level for shell-backed value providers (none=never execute, \
+                           prompt=confirm every command, ultimate=execute without asking).")
+                    .value_parser(["none", "prompt", "ultimate"])
+                    .default_value("none")
+                    .conflicts_with("trust"))
                 .arg(clap::Arg::new("trust")
                     .long("trust")
-                    .help("Enables the shell command execution. This is potentially insecure and should only be done for trustworthy sources.")
+                    .help("Alias for \"--shell-trust ultimate\". This is potentially insecure and should only be done for trustworthy sources.")
                     .action(ArgAction::SetTrue))
                 .arg(clap::Arg::new("loose")
                     .short('l')
@@ -141,10 +214,96 @@ impl ClapArgumentLoader {
                     .help("The execution backend (cli=native-terminal, ui=ui emulator in terminal).")
                     .value_parser(backend_values.clone())
                     .default_value("headless"))
+                .arg(clap::Arg::new("values-file")
+                    .long("values-file")
+                    .action(ArgAction::Append)
+                    .help("Imports value overrides from a YAML/JSON file containing a flat map of { key: value }. \
+                           Can be given multiple times, with later files winning."))
+                .arg(clap::Arg::new("env-prefix")
+                    .long("env-prefix")
+                    .help("Imports value overrides from environment variables matching PREFIX_key, e.g. \
+                           \"COMPLATE_VAR\" imports \"COMPLATE_VAR_foo\" as \"foo\"."))
                 .arg(clap::Arg::new("value")
                     .short('v')
                     .long("value")
-                    .help("Overrides a certain value definition with a string.")))
+                    .action(ArgAction::Append)
+                    .help("Overrides a certain value definition with a string. Takes precedence over \
+                           --values-file and --env-prefix.")))
+    }
+
+    fn discover_config(start: &std::path::Path) -> Result<String, Box<dyn std::error::Error>> {
+        let mut searched = Vec::new();
+        let mut dir = Some(start);
+        while let Some(d) = dir {
+            let candidate = d.join(".complate").join("config.yaml");
+            if candidate.is_file() {
+                return Ok(candidate.to_string_lossy().into_owned());
+            }
+            searched.push(d.to_string_lossy().into_owned());
+            dir = d.parent();
+        }
+
+        Err(Box::new(Error::Argument(format!(
+            "could not find \".complate/config.yaml\" in any of the following directories: {}",
+            searched.join(", ")
+        ))))
+    }
+
+    fn scalar_to_string(value: &serde_yaml::Value) -> Result<String, Box<dyn std::error::Error>> {
+        match value {
+            | serde_yaml::Value::String(s) => Ok(s.clone()),
+            | serde_yaml::Value::Bool(b) => Ok(b.to_string()),
+            | serde_yaml::Value::Number(n) => Ok(n.to_string()),
+            | serde_yaml::Value::Null => Ok(String::new()),
+            | serde_yaml::Value::Sequence(_) | serde_yaml::Value::Mapping(_) => Err(Box::new(Error::Argument(
+                "values-file entries must be scalars (string, number or bool)".into(),
+            ))),
+            | _ => Err(Box::new(Error::Argument("unsupported values-file entry type".into()))),
+        }
+    }
+
+    fn env_overrides(prefix: &str, vars: impl IntoIterator<Item = (String, String)>) -> HashMap<String, String> {
+        let prefix = format!("{}_", prefix);
+        vars.into_iter().filter_map(|(k, v)| k.strip_prefix(prefix.as_str()).map(|key| (key.to_owned(), v))).collect()
+    }
+
+    fn merge_layered(
+        base: HashMap<String, String>,
+        layers: impl IntoIterator<Item = HashMap<String, String>>,
+    ) -> HashMap<String, String> {
+        let mut merged = base;
+        for layer in layers {
+            merged.extend(layer);
+        }
+        merged
+    }
+
+    fn template_identifiers(config_content: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        #[derive(serde::Deserialize)]
+        struct Config {
+            #[serde(default)]
+            templates: std::collections::BTreeMap<String, serde_yaml::Value>,
+        }
+
+        let config: Config = serde_yaml::from_str(config_content)?;
+        Ok(config.templates.into_keys().collect())
+    }
+
+    pub fn root_command_with_templates(template_ids: Vec<String>) -> clap::Command {
+        // clap's `PossibleValuesParser` needs `'static` strings without the (unenabled) "string"
+        // feature; leaking is fine here since this only runs once, right before process exit, to
+        // emit a completion script.
+        let template_ids: Vec<&'static str> =
+            template_ids.into_iter().map(|id| &*Box::leak(id.into_boxed_str())).collect();
+
+        let mut root = Self::root_command();
+        if let Some(render) = root.find_subcommand_mut("render") {
+            let replaced = std::mem::replace(render, clap::Command::new("render"));
+            *render = replaced.mut_arg("template", |arg| {
+                arg.value_parser(clap::builder::PossibleValuesParser::new(template_ids))
+            });
+        }
+        root
     }
 
     pub async fn load() -> Result<CallArgs, Box<dyn std::error::Error>> {
@@ -170,10 +329,16 @@ impl ClapArgumentLoader {
                 privileges,
             })
         } else if let Some(subc) = command_matches.subcommand_matches("autocomplete") {
+            let template_ids = match subc.get_one::<String>("config") {
+                | Some(config) => Self::template_identifiers(&std::fs::read_to_string(config)?)?,
+                | None => Vec::new(),
+            };
+
             Ok(CallArgs {
                 command: Command::Autocomplete {
                     path: subc.get_one::<String>("out").unwrap().into(),
                     shell: clap_complete::Shell::from_str(subc.get_one::<String>("shell").unwrap().as_str()).unwrap(),
+                    template_ids,
                 },
                 privileges,
             })
@@ -183,23 +348,50 @@ impl ClapArgumentLoader {
                 privileges,
             })
         } else if let Some(subc) = command_matches.subcommand_matches("render") {
-            let config = std::fs::read_to_string(subc.get_one::<String>("config").unwrap())?;
+            let config_path = match subc.get_one::<String>("config") {
+                | Some(explicit) => explicit.clone(),
+                | None if subc.get_flag("no-discover") => "./.complate/config.yaml".to_owned(),
+                | None => Self::discover_config(&std::env::current_dir()?)?,
+            };
+            let config = std::fs::read_to_string(config_path)?;
             let template = subc.get_one::<String>("template").map(|v| v.into());
             let shell_trust = if subc.get_flag("trust") {
                 ShellTrust::Ultimate
             } else {
-                ShellTrust::None
+                match subc.get_one::<String>("shell-trust").unwrap().as_str() {
+                    | "none" => ShellTrust::None,
+                    | "prompt" => ShellTrust::Prompt,
+                    | "ultimate" => ShellTrust::Ultimate,
+                    | _ => return Err(Box::new(Error::Argument("unknown shell-trust level".into()))),
+                }
             };
             let loose = subc.get_flag("loose");
 
-            let mut value_overrides = HashMap::<String, String>::new();
+            let mut values_file_overrides = HashMap::<String, String>::new();
+            if let Some(files) = subc.get_many::<String>("values-file") {
+                for file in files {
+                    let content = std::fs::read_to_string(file)?;
+                    let parsed: HashMap<String, serde_yaml::Value> = serde_yaml::from_str(&content)?;
+                    for (k, v) in parsed {
+                        values_file_overrides.insert(k, Self::scalar_to_string(&v)?);
+                    }
+                }
+            }
+            let env_overrides = match subc.get_one::<String>("env-prefix") {
+                | Some(prefix) => Self::env_overrides(prefix, std::env::vars()),
+                | None => HashMap::new(),
+            };
+            let mut explicit_overrides = HashMap::<String, String>::new();
             if let Some(vo_arg) = subc.get_many::<String>("value") {
                 for vo in vo_arg {
                     let spl = vo.splitn(2, "=").collect::<Vec<_>>();
-                    value_overrides.insert(spl[0].into(), spl[1].into());
+                    explicit_overrides.insert(spl[0].into(), spl[1].into());
                 }
             }
-            let backend = match subc.get_one::<String>("backend").unwrap().as_str() {
+            // precedence: defaults < values-file < env-prefix < explicit -v
+            let value_overrides =
+                Self::merge_layered(HashMap::new(), [values_file_overrides, env_overrides, explicit_overrides]);
+            let mut backend = match subc.get_one::<String>("backend").unwrap().as_str() {
                 | "headless" => Backend::Headless,
                 #[cfg(feature = "backend+cli")]
                 | "cli" => Backend::CLI,
@@ -208,6 +400,11 @@ impl ClapArgumentLoader {
                 | _ => return Err(Box::new(Error::Argument("no backend specified".into()))),
             };
 
+            let plain = PlainInfo::from_env();
+            if plain.is_plain && !plain.excepts("backend") {
+                backend = Backend::Headless;
+            }
+
             Ok(CallArgs {
                 privileges,
                 command: Command::Render(RenderArguments {
@@ -217,6 +414,7 @@ impl ClapArgumentLoader {
                     shell_trust,
                     loose,
                     backend,
+                    plain,
                 }),
             })
         } else {
@@ -224,3 +422,78 @@ impl ClapArgumentLoader {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discover_config_finds_nearest_ancestor() {
+        let root = std::env::temp_dir().join(format!("complate-discover-test-{}", std::process::id()));
+        let nested = root.join("a").join("b");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir_all(root.join(".complate")).unwrap();
+        std::fs::write(root.join(".complate").join("config.yaml"), "templates: {}").unwrap();
+
+        let found = ClapArgumentLoader::discover_config(&nested).unwrap();
+        assert_eq!(found, root.join(".complate").join("config.yaml").to_string_lossy());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn discover_config_errors_when_not_found() {
+        let root = std::env::temp_dir().join(format!("complate-discover-test-missing-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+
+        assert!(ClapArgumentLoader::discover_config(&root).is_err());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn env_overrides_strips_matching_prefix_only() {
+        let vars = [
+            ("COMPLATE_VAR_foo".to_owned(), "1".to_owned()),
+            ("COMPLATE_VAR_bar".to_owned(), "2".to_owned()),
+            ("OTHER_foo".to_owned(), "3".to_owned()),
+        ];
+
+        let overrides = ClapArgumentLoader::env_overrides("COMPLATE_VAR", vars);
+
+        assert_eq!(overrides.get("foo"), Some(&"1".to_owned()));
+        assert_eq!(overrides.get("bar"), Some(&"2".to_owned()));
+        assert_eq!(overrides.len(), 2);
+    }
+
+    #[test]
+    fn merge_layered_lets_later_layers_win() {
+        let base = HashMap::new();
+        let values_file = HashMap::from([("a".to_owned(), "file".to_owned()), ("b".to_owned(), "file".to_owned())]);
+        let env = HashMap::from([("b".to_owned(), "env".to_owned())]);
+        let explicit = HashMap::from([("a".to_owned(), "explicit".to_owned())]);
+
+        let merged = ClapArgumentLoader::merge_layered(base, [values_file, env, explicit]);
+
+        assert_eq!(merged.get("a"), Some(&"explicit".to_owned()));
+        assert_eq!(merged.get("b"), Some(&"env".to_owned()));
+    }
+
+    #[test]
+    fn shell_trust_none_never_confirms() {
+        assert!(!ShellTrust::None.confirm("echo hi").unwrap());
+    }
+
+    #[test]
+    fn shell_trust_ultimate_always_confirms() {
+        assert!(ShellTrust::Ultimate.confirm("echo hi").unwrap());
+    }
+
+    #[test]
+    fn shell_trust_prompt_parses_yes_variants() {
+        assert!(ShellTrust::answer_confirms("y"));
+        assert!(ShellTrust::answer_confirms("Yes\n"));
+        assert!(!ShellTrust::answer_confirms("n"));
+        assert!(!ShellTrust::answer_confirms(""));
+    }
+}